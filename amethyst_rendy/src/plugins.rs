@@ -3,20 +3,23 @@
 use amethyst_core::ecs::{DispatcherBuilder, Resources, World};
 use amethyst_error::Error;
 use palette::Srgb;
-use rendy::graph::render::RenderGroupDesc;
+use rendy::{
+    graph::render::RenderGroupDesc,
+    hal::command::{ClearColor, ClearDepthStencil, ClearValue},
+};
 #[cfg(feature = "window")]
 pub use window::RenderToWindow;
 
 use crate::{
     bundle,
-    bundle::{RenderOrder, RenderPlan, RenderPlugin, Target},
+    bundle::{ImageOptions, OutputColor, RenderOrder, RenderPlan, RenderPlugin, Target},
     pass::{
         Base3DPassDef, DrawBase3DDesc, DrawBase3DTransparentDesc, DrawDebugLinesDesc,
         DrawFlat2DDesc, DrawFlat2DTransparentDesc, DrawSkyboxDesc,
     },
     sprite_visibility::{SpriteVisibility, SpriteVisibilitySortingSystem},
     visibility::{Visibility, VisibilitySortingSystem},
-    Backend, Factory,
+    Backend, Factory, Format, Kind,
 };
 
 #[cfg(feature = "window")]
@@ -38,7 +41,9 @@ mod window {
 
     /// A [`RenderPlugin`] for opening a window and displaying a render target to it.
     ///
-    /// When you provide [`DisplayConfig`], it opens a window for you using [`WindowBundle`].
+    /// When you provide [`DisplayConfig`], it opens a window for you using [`WindowBundle`], then
+    /// presents to the singleton [`Window`]/[`ScreenDimensions`] resources it creates. Only that
+    /// one window is rendered to.
     #[derive(Default, Debug)]
     pub struct RenderToWindow {
         target: Target,
@@ -46,6 +51,7 @@ mod window {
         dimensions: Option<ScreenDimensions>,
         dirty: bool,
         clear: Option<ClearColor>,
+        transparent: bool,
     }
 
     impl RenderToWindow {
@@ -101,6 +107,24 @@ mod window {
             self.clear = Some(clear.into());
             self
         }
+
+        /// Set the transparency hint on [`DisplayConfig`] so the OS compositor (X11/Wayland/macOS)
+        /// treats the window as see-through, so regions cleared to zero alpha (see
+        /// [`Self::with_clear`]) show the desktop behind the window. The hint is applied only to
+        /// the config given to [`Self::from_config`]/[`Self::from_config_path`]; without one this
+        /// has no effect.
+        ///
+        /// Scope: this is the window-creation hint only. It does not request an alpha-capable
+        /// surface format and does not change the present pass's blend state to preserve straight
+        /// alpha, so content drawn with partial alpha over the cleared background composites as
+        /// opaque, not blended with the desktop. Surface-format negotiation and present-pass blend
+        /// state live outside this plugin (in `Factory`/the graph build), so implementing that is
+        /// out of scope here; this method only ever does what's documented above.
+        #[must_use]
+        pub fn with_transparency(mut self) -> Self {
+            self.transparent = true;
+            self
+        }
     }
 
     impl<B: Backend> RenderPlugin<B> for RenderToWindow {
@@ -110,7 +134,8 @@ mod window {
             resources: &mut Resources,
             builder: &mut DispatcherBuilder,
         ) -> Result<(), Error> {
-            if let Some(config) = self.config.take() {
+            if let Some(mut config) = self.config.take() {
+                config.transparent |= self.transparent;
                 builder.add_bundle(WindowBundle::from_config(config));
             }
 
@@ -119,10 +144,10 @@ mod window {
 
         #[allow(clippy::map_clone)]
         fn should_rebuild(&mut self, world: &World, resources: &Resources) -> bool {
-            let new_dimensions = resources.get::<ScreenDimensions>();
-            if self.dimensions.as_ref() != new_dimensions.as_deref() {
+            let new_dimensions = resources.get::<ScreenDimensions>().map(|d| (*d).clone());
+            if self.dimensions != new_dimensions {
                 self.dirty = true;
-                self.dimensions = new_dimensions.map(|d| (*d).clone());
+                self.dimensions = new_dimensions;
                 return false;
             }
             self.dirty
@@ -137,9 +162,13 @@ mod window {
         ) -> Result<(), Error> {
             self.dirty = false;
 
-            let window = resources.get::<Window>().unwrap();
             // Explicitly deref, so we get a type that implements HasRawWindowHandle.
-            let window: &Window = &window;
+            let singleton = resources.get::<Window>();
+            let window: &Window = singleton.as_deref().ok_or_else(|| {
+                Error::from_string(
+                    "RenderToWindow: no Window resource present; is WindowBundle installed?",
+                )
+            })?;
             let surface = factory.create_surface(window)?;
             let dimensions = self.dimensions.as_ref().unwrap();
             let window_kind = Kind::D2(dimensions.width() as u32, dimensions.height() as u32, 1, 1);
@@ -173,6 +202,116 @@ mod window {
     }
 }
 
+/// A [`RenderPlugin`] for rendering to an off-screen texture instead of a window surface.
+///
+/// Unlike [`RenderToWindow`], the [`Target`] this defines is never a root target: its color (and
+/// optional depth) attachments are [`OutputColor::Image`]s rather than a swapchain `Surface`.
+/// Later passes consume the resulting image the same way they consume any other target's
+/// image, by referencing this plugin's [`Target`] (via `crate::bundle::TargetImage`, the same
+/// path used to read any non-root target's output) — there is no separate name-keyed registry
+/// to publish into. Make sure some other plugin actually reads this [`Target`]; an image nothing
+/// consumes is still sized and allocated, but rendering to it does nothing useful.
+#[derive(Debug)]
+pub struct RenderToTexture {
+    target: Target,
+    dimensions: (u32, u32),
+    format: Format,
+    clear: Option<ClearColor>,
+    clear_depth_stencil: Option<ClearDepthStencil>,
+    dirty: bool,
+}
+
+impl RenderToTexture {
+    /// Create a `RenderToTexture` plugin sized to `dimensions`. Select which [`Target`] it
+    /// writes to with [`Self::with_target`]; later passes read that target's image directly.
+    pub fn new(dimensions: (u32, u32)) -> Self {
+        Self {
+            target: Target::default(),
+            dimensions,
+            format: Format::Rgba8Unorm,
+            clear: None,
+            clear_depth_stencil: None,
+            dirty: true,
+        }
+    }
+
+    /// Select the (non-root) render target that will be written into the texture.
+    #[must_use]
+    pub fn with_target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Resize the texture. Defaults to the dimensions passed to [`Self::new`].
+    #[must_use]
+    pub fn with_dimensions(mut self, dimensions: (u32, u32)) -> Self {
+        self.dimensions = dimensions;
+        self.dirty = true;
+        self
+    }
+
+    /// Override the color format of the texture. Defaults to `Format::Rgba8Unorm`.
+    #[must_use]
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Clear the color output with the given color every frame.
+    #[must_use]
+    pub fn with_clear(mut self, clear: impl Into<ClearColor>) -> Self {
+        self.clear = Some(clear.into());
+        self
+    }
+
+    /// Attach a depth/stencil buffer and clear it with the given value every frame.
+    #[must_use]
+    pub fn with_clear_depth_stencil(mut self, clear: ClearDepthStencil) -> Self {
+        self.clear_depth_stencil = Some(clear);
+        self
+    }
+}
+
+impl<B: Backend> RenderPlugin<B> for RenderToTexture {
+    fn should_rebuild(&mut self, _world: &World, _resources: &Resources) -> bool {
+        std::mem::replace(&mut self.dirty, false)
+    }
+
+    fn on_plan(
+        &mut self,
+        plan: &mut RenderPlan<B>,
+        _factory: &mut Factory<B>,
+        _world: &World,
+        _resources: &Resources,
+    ) -> Result<(), Error> {
+        let kind = Kind::D2(self.dimensions.0, self.dimensions.1, 1, 1);
+
+        let color_options = ImageOptions {
+            kind,
+            levels: 1,
+            format: self.format,
+            clear: self.clear.map(|color| ClearValue { color }),
+        };
+
+        let depth = self.clear_depth_stencil.map(|depth_stencil| ImageOptions {
+            kind,
+            levels: 1,
+            format: Format::D32Sfloat,
+            clear: Some(ClearValue { depth_stencil }),
+        });
+
+        plan.define_pass(
+            self.target,
+            crate::bundle::TargetPlanOutputs {
+                colors: vec![OutputColor::Image(color_options)],
+                depth,
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
 /// A `RenderPlugin` for forward rendering of 3d objects using flat shading.
 pub type RenderFlat3D = RenderBase3D<crate::pass::FlatPassDef>;
 /// A `RenderPlugin` for forward rendering of 3d objects using shaded shading.